@@ -0,0 +1,110 @@
+//! Line/delimiter framing for request-response serial protocols.
+//!
+//! Many serial protocols are simple newline-terminated request/response
+//! exchanges (e.g. `foo?\n` -> `foo=1\n`). `DelimiterCodec` and `LineCodec`
+//! implement `tokio_core::io::Codec` so such protocols can be framed with
+//! `SerialPort::framed` instead of every user reimplementing the scanning
+//! and buffering by hand.
+
+use std::io;
+use std::str;
+
+use tokio_core::io::{Codec, EasyBuf};
+
+/// A codec that frames messages on an arbitrary delimiter byte.
+///
+/// Decoding scans the accumulated buffer for `delim`; once found, everything up
+/// to and including the delimiter is split off, the delimiter is stripped, and
+/// the remainder is decoded as a UTF-8 `String` frame. Bytes after the
+/// delimiter are left in the buffer, so multiple frames buffered in a single
+/// read are yielded one at a time across repeated calls to `decode`.
+pub struct DelimiterCodec {
+    /// The byte that marks the end of a frame.
+    pub delim: u8,
+}
+
+impl Codec for DelimiterCodec {
+    type In = String;
+    type Out = String;
+
+    fn decode(&mut self, buf: &mut EasyBuf) -> io::Result<Option<String>> {
+        if let Some(i) = buf.as_slice().iter().position(|&b| b == self.delim) {
+            let line = buf.drain_to(i + 1);
+            let line = &line.as_slice()[..i];
+
+            return match str::from_utf8(line) {
+                Ok(s) => Ok(Some(s.to_string())),
+                Err(_) => Err(io::Error::new(io::ErrorKind::InvalidData, "invalid UTF-8")),
+            };
+        }
+
+        Ok(None)
+    }
+
+    fn encode(&mut self, msg: String, buf: &mut Vec<u8>) -> io::Result<()> {
+        buf.extend(msg.as_bytes());
+        buf.push(self.delim);
+        Ok(())
+    }
+}
+
+/// A codec that frames messages on `\n`, the common case for line-oriented
+/// serial protocols.
+pub struct LineCodec;
+
+impl Codec for LineCodec {
+    type In = String;
+    type Out = String;
+
+    fn decode(&mut self, buf: &mut EasyBuf) -> io::Result<Option<String>> {
+        DelimiterCodec { delim: b'\n' }.decode(buf)
+    }
+
+    fn encode(&mut self, msg: String, buf: &mut Vec<u8>) -> io::Result<()> {
+        DelimiterCodec { delim: b'\n' }.encode(msg, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_core::io::{Codec, EasyBuf};
+
+    use super::{DelimiterCodec, LineCodec};
+
+    #[test]
+    fn decodes_multiple_frames_buffered_in_one_read() {
+        let mut codec = LineCodec;
+        let mut buf = EasyBuf::from(b"ab\ncd\n".to_vec());
+
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some("ab".to_string()));
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some("cd".to_string()));
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    }
+
+    #[test]
+    fn leaves_partial_frame_in_buffer_until_more_bytes_arrive() {
+        let mut codec = LineCodec;
+        let mut buf = EasyBuf::from(b"ab".to_vec());
+
+        // First read only delivers a partial frame: no delimiter yet, so decode
+        // must wait rather than producing (or discarding) a frame, leaving the
+        // bytes it was given untouched in the buffer.
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        assert_eq!(buf.as_slice(), b"ab");
+
+        // The next read appends more bytes (including the delimiter) to what's
+        // left over from the first read; decode should now see the full frame.
+        let mut rest = buf.as_slice().to_vec();
+        rest.extend_from_slice(b"cd\n");
+        buf = EasyBuf::from(rest);
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some("abcd".to_string()));
+    }
+
+    #[test]
+    fn errors_on_invalid_utf8() {
+        let mut codec = DelimiterCodec { delim: b'\n' };
+        let mut buf = EasyBuf::from(vec![0xff, 0xfe, b'\n']);
+
+        assert!(codec.decode(&mut buf).is_err());
+    }
+}
@@ -10,6 +10,8 @@ extern crate futures;
 #[macro_use]
 extern crate tokio_core;
 extern crate mio_serial;
+#[cfg(unix)]
+extern crate termios;
 
 use std::ffi::OsStr;
 use std::fmt;
@@ -17,11 +19,20 @@ use std::io::{self, Read, Write};
 
 use futures::Async;
 use tokio_core::reactor::{PollEvented, Handle};
-use tokio_core::io::Io;
+use tokio_core::io::{Io, Codec, Framed};
+
+pub mod codec;
+
+/// The readable half of a `SerialPort`, created with `SerialPort::split`.
+pub type ReadHalf = tokio_core::io::ReadHalf<SerialPort>;
+
+/// The writable half of a `SerialPort`, created with `SerialPort::split`.
+pub type WriteHalf = tokio_core::io::WriteHalf<SerialPort>;
 
 // Re-exports
 pub use mio_serial::PortSettings;
 pub use mio_serial::{BaudRate, CharSize, Parity, StopBits, FlowControl};
+pub use mio_serial::ClearBuffer;
 
 /// A structure representing an open serial port.
 pub struct SerialPort {
@@ -47,6 +58,9 @@ impl SerialPort {
     }
 
     fn new(port: mio_serial::SerialPort, handle: &Handle) -> io::Result<SerialPort> {
+        #[cfg(unix)]
+        try!(sys::set_non_blocking_timing(&port));
+
         let io = try!(PollEvented::new(port, handle));
         Ok(SerialPort { io: io })
     }
@@ -70,11 +84,127 @@ impl SerialPort {
     pub fn poll_write(&self) -> Async<()> {
         self.io.poll_write()
     }
+
+    /// Sets the state of the RTS (Request To Send) control signal.
+    pub fn set_rts(&mut self, level: bool) -> io::Result<()> {
+        self.io.get_ref().set_rts(level)
+    }
+
+    /// Sets the state of the DTR (Data Terminal Ready) control signal.
+    pub fn set_dtr(&mut self, level: bool) -> io::Result<()> {
+        self.io.get_ref().set_dtr(level)
+    }
+
+    /// Reads the state of the CTS (Clear To Send) control signal.
+    pub fn read_cts(&self) -> io::Result<bool> {
+        self.io.get_ref().read_cts()
+    }
+
+    /// Reads the state of the DSR (Data Set Ready) control signal.
+    pub fn read_dsr(&self) -> io::Result<bool> {
+        self.io.get_ref().read_dsr()
+    }
+
+    /// Reads the state of the CD (Carrier Detect) control signal.
+    pub fn read_carrier_detect(&self) -> io::Result<bool> {
+        self.io.get_ref().read_carrier_detect()
+    }
+
+    /// Reads the state of the RI (Ring Indicator) control signal.
+    pub fn read_ring_indicator(&self) -> io::Result<bool> {
+        self.io.get_ref().read_ring_indicator()
+    }
+
+    /// Discards bytes queued in the OS driver's input and/or output buffers.
+    pub fn clear(&self, buffer: ClearBuffer) -> io::Result<()> {
+        self.io.get_ref().clear(buffer)
+    }
+
+    /// Returns the settings currently applied to this port.
+    pub fn settings(&self) -> PortSettings {
+        self.io.get_ref().settings()
+    }
+
+    /// Applies new settings to the already-open port, without dropping the
+    /// reactor registration.
+    pub fn reconfigure(&mut self, settings: &PortSettings) -> io::Result<()> {
+        try!(self.io.get_mut().reconfigure(settings));
+        self.reapply_non_blocking_timing()
+    }
+
+    /// Sets the baud rate.
+    pub fn set_baud_rate(&mut self, baud_rate: BaudRate) -> io::Result<()> {
+        try!(self.io.get_mut().set_baud_rate(baud_rate));
+        self.reapply_non_blocking_timing()
+    }
+
+    /// Sets the character size.
+    pub fn set_char_size(&mut self, char_size: CharSize) -> io::Result<()> {
+        try!(self.io.get_mut().set_char_size(char_size));
+        self.reapply_non_blocking_timing()
+    }
+
+    /// Sets the parity.
+    pub fn set_parity(&mut self, parity: Parity) -> io::Result<()> {
+        try!(self.io.get_mut().set_parity(parity));
+        self.reapply_non_blocking_timing()
+    }
+
+    /// Sets the number of stop bits.
+    pub fn set_stop_bits(&mut self, stop_bits: StopBits) -> io::Result<()> {
+        try!(self.io.get_mut().set_stop_bits(stop_bits));
+        self.reapply_non_blocking_timing()
+    }
+
+    /// Sets the flow control mode.
+    pub fn set_flow_control(&mut self, flow_control: FlowControl) -> io::Result<()> {
+        try!(self.io.get_mut().set_flow_control(flow_control));
+        self.reapply_non_blocking_timing()
+    }
+
+    /// Re-applies the VMIN=0/VTIME=0 non-blocking read timing set up in `new()`.
+    ///
+    /// `mio_serial`'s settings mutators rebuild termios from scratch, which
+    /// would otherwise undo this and revert the port to blocking/timed-out
+    /// reads; every settings mutator must call this after applying its change.
+    fn reapply_non_blocking_timing(&self) -> io::Result<()> {
+        #[cfg(unix)]
+        try!(sys::set_non_blocking_timing(self.io.get_ref()));
+
+        Ok(())
+    }
+
+    /// Splits this `SerialPort` into a read half and a write half, which can be
+    /// used to read and write the port concurrently from separate tasks, e.g.
+    /// `io::copy(read_half, stdout).select(io::copy(stdin, write_half))`.
+    pub fn split(self) -> (ReadHalf, WriteHalf) {
+        Io::split(self)
+    }
+
+    /// Wraps this `SerialPort` in a framed transport using `codec` to decode
+    /// incoming bytes into frames and encode outgoing frames into bytes.
+    ///
+    /// See the `codec` module for the built-in `LineCodec` and
+    /// `DelimiterCodec`, useful for newline-terminated request/response
+    /// protocols.
+    pub fn framed<C: Codec>(self, codec: C) -> Framed<SerialPort, C> {
+        Io::framed(self, codec)
+    }
 }
 
 impl Read for SerialPort {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.io.read(buf)
+        match self.io.read(buf) {
+            Ok(0) if !buf.is_empty() => {
+                self.io.need_read();
+                Err(io::Error::new(io::ErrorKind::WouldBlock, "no data available"))
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::TimedOut => {
+                self.io.need_read();
+                Err(io::Error::new(io::ErrorKind::WouldBlock, "no data available"))
+            }
+            other => other,
+        }
     }
 }
 
@@ -100,7 +230,17 @@ impl Io for SerialPort {
 
 impl<'a> Read for &'a SerialPort {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        (&self.io).read(buf)
+        match (&self.io).read(buf) {
+            Ok(0) if !buf.is_empty() => {
+                self.io.need_read();
+                Err(io::Error::new(io::ErrorKind::WouldBlock, "no data available"))
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::TimedOut => {
+                self.io.need_read();
+                Err(io::Error::new(io::ErrorKind::WouldBlock, "no data available"))
+            }
+            other => other,
+        }
     }
 }
 
@@ -132,7 +272,11 @@ impl fmt::Debug for SerialPort {
 
 #[cfg(unix)]
 mod sys {
+    use std::io;
     use std::os::unix::prelude::*;
+
+    use termios::{Termios, VMIN, VTIME, tcsetattr, TCSANOW};
+
     use super::SerialPort;
 
     impl AsRawFd for SerialPort {
@@ -140,6 +284,29 @@ mod sys {
             self.io.get_ref().as_raw_fd()
         }
     }
+
+    /// Puts the underlying tty into fully non-blocking reads (VMIN=0, VTIME=0), so
+    /// that a read with no data available returns immediately instead of blocking
+    /// or waiting out the driver's read timeout.
+    pub fn set_non_blocking_timing<T: AsRawFd>(port: &T) -> io::Result<()> {
+        let fd = port.as_raw_fd();
+        let mut termios = try!(Termios::from_fd(fd));
+        termios.c_cc[VMIN] = 0;
+        termios.c_cc[VTIME] = 0;
+        tcsetattr(fd, TCSANOW, &termios)
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::os::windows::prelude::*;
+    use super::SerialPort;
+
+    impl AsRawHandle for SerialPort {
+        fn as_raw_handle(&self) -> RawHandle {
+            self.io.get_ref().as_raw_handle()
+        }
+    }
 }
 
 #[cfg(test)]